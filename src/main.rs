@@ -6,14 +6,40 @@ mod ui;
 
 /// Interactive text UI for searching file contents.
 ///
-/// Start typing a regular expression and see the results in real time. Use
-/// Ctrl-C to clear the current search and Esc to quit the program.
+/// Start typing a regular expression and see the results in real time. Tab
+/// switches focus between the pattern field and the results list, and F2
+/// opens the file-type picker. While searching, Ctrl-T toggles fuzzy
+/// matching, Alt-i/Ctrl-w/Ctrl-f toggle case sensitivity/whole-word/
+/// fixed-string matching, and Ctrl-Up/Ctrl-Down grow and shrink the context
+/// shown around each match. With a result selected, Enter opens it in
+/// `$EDITOR`. Ctrl-C clears the current search and Esc quits the program.
 #[derive(Debug, FromArgs)]
 struct Opts {
     /// pattern to search for
     #[argh(option, short = 'p')]
     pattern: Option<String>,
 
+    /// only search files of this type (may be repeated); press F2 in the
+    /// app to pick from the full list of type names ripgrep knows about
+    #[argh(option, short = 't')]
+    r#type: Vec<String>,
+
+    /// skip files of this type (may be repeated)
+    #[argh(option, short = 'T')]
+    type_not: Vec<String>,
+
+    /// lines of context to show before and after each match
+    #[argh(option, short = 'C', default = "0")]
+    context: usize,
+
+    /// lines of context to show before each match (overrides -C)
+    #[argh(option, short = 'B')]
+    before_context: Option<usize>,
+
+    /// lines of context to show after each match (overrides -C)
+    #[argh(option, short = 'A')]
+    after_context: Option<usize>,
+
     /// list of paths to search
     #[argh(positional)]
     search_paths: Vec<OsString>,
@@ -25,7 +51,13 @@ fn main() -> eyre::Result<()> {
     let search_paths =
         if !opts.search_paths.is_empty() { opts.search_paths } else { vec![OsString::from(".")] };
 
-    let mut app = ui::App::new(search_paths, opts.pattern)?;
+    let type_filter = ui::TypeFilter { include: opts.r#type, exclude: opts.type_not };
+    let context = ui::ContextLines {
+        before: opts.before_context.unwrap_or(opts.context),
+        after: opts.after_context.unwrap_or(opts.context),
+    };
+
+    let mut app = ui::App::new(search_paths, opts.pattern, type_filter, context)?;
     app.render()?;
 
     Ok(())
@@ -1,6 +1,10 @@
 use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap},
     ffi::{OsStr, OsString},
     io::{self, Write},
+    path::Path,
+    process::Command,
     sync::Arc,
     thread,
     time::{Duration, Instant},
@@ -13,33 +17,511 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode},
 };
 use grep::{
-    regex::RegexMatcher,
-    searcher::{BinaryDetection, Searcher, SearcherBuilder, Sink, SinkError, SinkMatch},
+    matcher::Matcher,
+    regex::{RegexMatcher, RegexMatcherBuilder},
+    searcher::{
+        BinaryDetection, Searcher, SearcherBuilder, Sink, SinkContext, SinkError, SinkMatch,
+    },
 };
-use ignore::{DirEntry, WalkBuilder, WalkState};
+use ignore::{
+    DirEntry, WalkBuilder, WalkState,
+    types::{Types, TypesBuilder},
+};
+use nucleo_matcher::{Config as FuzzyConfig, Matcher as FuzzyMatcher, Utf32Str};
 use parking_lot::{Condvar, Mutex};
 use ratatui::{
     Terminal,
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Position, Rect},
-    style::{Color, Style},
-    text::Text,
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{FontStyle, ThemeSet},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
 };
 use unicode_width::UnicodeWidthStr;
 
 const TICK_RATE: Duration = Duration::from_millis(100);
 
+/// How long the pattern must be stable before a new search is fired, so a
+/// burst of keystrokes (or key autorepeat) doesn't restart the walk on
+/// every character.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(120);
+
+/// Lines of source shown above/below the matched line in the preview pane.
+const PREVIEW_WINDOW: usize = 12;
+
+/// Theme used to highlight the preview pane.
+const PREVIEW_THEME: &str = "base16-ocean.dark";
+
+/// Maximum number of ranked hits kept in `SearchMode::Fuzzy`, so scoring a
+/// large tree doesn't grow the results list without bound.
+const FUZZY_RESULT_LIMIT: usize = 500;
+
 enum UiEvent {
     Input(KeyEvent),
-    MatchFound { path: OsString, line: u64, text: String },
+    MatchFound {
+        path: OsString,
+        line: u64,
+        text: String,
+        spans: Vec<(usize, usize)>,
+        /// The `nucleo-matcher` score in `SearchMode::Fuzzy`, `None` in
+        /// `SearchMode::Regex` (where every match is equally "relevant").
+        score: Option<u32>,
+        generation: u64,
+    },
+    ContextFound {
+        path: OsString,
+        line: u64,
+        text: String,
+        generation: u64,
+    },
+    ContextBreak {
+        path: OsString,
+        generation: u64,
+    },
     Paste(String),
     Tick,
 }
 
+/// A single search hit, kept structured so the UI can act on its path and
+/// line number instead of just displaying them.
+#[derive(Clone)]
+struct Match {
+    path: OsString,
+    line: u64,
+    text: String,
+    /// Char offsets (not byte offsets) of the substrings in `text` that the
+    /// pattern matched, for highlighting.
+    spans: Vec<(usize, usize)>,
+}
+
+/// One scored hit in `SearchMode::Fuzzy`. Ordered so the lowest-scoring
+/// entry sorts least, which is what lets a `BinaryHeap` evict it first once
+/// the bounded top-N list (`FUZZY_RESULT_LIMIT`) is full; ties fall back to
+/// path then line so the ranking is deterministic.
+struct FuzzyHit {
+    score: u32,
+    path: OsString,
+    line: u64,
+    text: String,
+    spans: Vec<(usize, usize)>,
+}
+
+impl PartialEq for FuzzyHit {
+    fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+}
+
+impl Eq for FuzzyHit {}
+
+impl PartialOrd for FuzzyHit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for FuzzyHit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| other.path.cmp(&self.path))
+            .then_with(|| other.line.cmp(&self.line))
+    }
+}
+
+/// One row of the results list: a match, a dimmed context line around it, or
+/// a separator between two non-adjacent groups.
+#[derive(Clone)]
+enum ResultLine {
+    Match(Match),
+    Context { path: OsString, line: u64, text: String },
+    Separator,
+}
+
+/// Accumulates result lines per source file, keyed on the path each event
+/// names rather than channel arrival order.
+///
+/// `walker.build_parallel()` runs one thread per file, so while a single
+/// file's own events always arrive in order relative to each other, threads
+/// for *different* files interleave freely on the shared channel -- pushing
+/// straight onto a flat `Vec<ResultLine>` as events arrive would splice one
+/// file's context block into the middle of another's. Buffering by path
+/// keeps every file's lines contiguous, and `flatten` is what turns that
+/// into the rendered list, inserting a separator at every file boundary in
+/// addition to the ones `context_break` already records within a file.
+#[derive(Default)]
+struct ResultGroups {
+    order: Vec<OsString>,
+    by_path: HashMap<OsString, Vec<ResultLine>>,
+}
+
+impl ResultGroups {
+    fn clear(&mut self) {
+        self.order.clear();
+        self.by_path.clear();
+    }
+
+    fn group_mut(&mut self, path: &OsStr) -> &mut Vec<ResultLine> {
+        if !self.by_path.contains_key(path) {
+            self.order.push(path.to_owned());
+            self.by_path.insert(path.to_owned(), Vec::new());
+        }
+        self.by_path.get_mut(path).expect("just inserted")
+    }
+
+    fn push_line(&mut self, path: &OsStr, line: ResultLine) { self.group_mut(path).push(line); }
+
+    /// Record a gap within `path`'s own group, as reported by `Searcher`.
+    /// A no-op if the group is empty or already ends in a separator, so file
+    /// boundaries (added in `flatten`) aren't doubled up.
+    fn push_break(&mut self, path: &OsStr) {
+        let group = self.group_mut(path);
+        if matches!(group.last(), None | Some(ResultLine::Separator)) {
+            return;
+        }
+        group.push(ResultLine::Separator);
+    }
+
+    /// Flatten every file's group, in first-seen order, into the rows the
+    /// results list renders, separating non-adjacent groups -- which every
+    /// pair of distinct files is, by definition.
+    fn flatten(&self) -> Vec<ResultLine> {
+        let mut out = Vec::new();
+        for path in &self.order {
+            let Some(group) = self.by_path.get(path) else { continue };
+            if group.is_empty() {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push(ResultLine::Separator);
+            }
+            out.extend(group.iter().cloned());
+        }
+        out
+    }
+}
+
+/// How many lines of context to show before/after each match, like
+/// `rg -A`/`-B`/`-C`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContextLines {
+    pub before: usize,
+    pub after: usize,
+}
+
+/// A syntax-highlighted rendering of the file around the currently selected
+/// match, cached so it's only rebuilt when the selection changes.
+///
+/// Keyed on the match's `(path, line)` identity rather than its row index --
+/// `results` can be rebuilt (a new search, or a re-ranked fuzzy hit list)
+/// without the selected index moving, which would otherwise fool the cache
+/// into keeping a stale render around.
+struct Preview {
+    path: OsString,
+    line: u64,
+    lines: Vec<Line<'static>>,
+}
+
+fn convert_color(color: syntect::highlighting::Color) -> Color {
+    Color::Rgb(color.r, color.g, color.b)
+}
+
+/// Load `path` and syntax-highlight a window of `PREVIEW_WINDOW` lines
+/// around `matched_line`, marking that line with a gutter indicator.
+fn highlight_preview(
+    syntax_set: &SyntaxSet,
+    theme_set: &ThemeSet,
+    path: &OsStr,
+    matched_line: u64,
+) -> eyre::Result<Vec<Line<'static>>> {
+    let contents = std::fs::read_to_string(Path::new(path))?;
+    let syntax = syntax_set
+        .find_syntax_for_file(Path::new(path))?
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+    let theme = &theme_set.themes[PREVIEW_THEME];
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let matched_line = matched_line as usize;
+    let start = matched_line.saturating_sub(PREVIEW_WINDOW).max(1);
+    let end = matched_line + PREVIEW_WINDOW;
+
+    let mut lines = Vec::new();
+    for (number, text) in LinesWithEndings::from(&contents).enumerate() {
+        let number = number + 1;
+        let ranges = highlighter.highlight_line(text, syntax_set)?;
+        if number < start {
+            continue;
+        }
+        if number > end {
+            break;
+        }
+
+        let gutter = if number == matched_line { "> " } else { "  " };
+        let mut spans = vec![Span::styled(
+            format!("{gutter}{number:>5} "),
+            Style::default().fg(Color::DarkGray),
+        )];
+        spans.extend(ranges.into_iter().map(|(style, text)| {
+            Span::styled(
+                text.trim_end_matches(['\n', '\r']).to_string(),
+                Style::default()
+                    .fg(convert_color(style.foreground))
+                    .add_modifier(convert_font_style(style.font_style)),
+            )
+        }));
+        lines.push(Line::from(spans));
+    }
+
+    Ok(lines)
+}
+
+fn convert_font_style(style: FontStyle) -> Modifier {
+    let mut modifier = Modifier::empty();
+    if style.contains(FontStyle::BOLD) {
+        modifier |= Modifier::BOLD;
+    }
+    if style.contains(FontStyle::ITALIC) {
+        modifier |= Modifier::ITALIC;
+    }
+    if style.contains(FontStyle::UNDERLINE) {
+        modifier |= Modifier::UNDERLINED;
+    }
+    modifier
+}
+
+/// Split `text` into alternating plain/highlighted spans according to the
+/// (char-offset) match ranges in `spans`.
+fn highlight_spans(text: &str, spans: &[(usize, usize)]) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = Vec::new();
+    let mut pos = 0;
+
+    for &(start, end) in spans {
+        let start = start.min(chars.len());
+        let end = end.clamp(start, chars.len());
+
+        if start > pos {
+            out.push(Span::raw(chars[pos..start].iter().collect::<String>()));
+        }
+        if end > start {
+            out.push(Span::styled(
+                chars[start..end].iter().collect::<String>(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        pos = end.max(pos);
+    }
+
+    if pos < chars.len() {
+        out.push(Span::raw(chars[pos..].iter().collect::<String>()));
+    }
+
+    out
+}
+
+/// Turn the (sorted, ascending) char indices `nucleo-matcher` reports for a
+/// fuzzy match into the `(start, end)` ranges `highlight_spans` expects,
+/// merging adjacent indices into a single run.
+fn fuzzy_indices_to_spans(indices: &[u32]) -> Vec<(usize, usize)> {
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for &index in indices {
+        let index = index as usize;
+        match spans.last_mut() {
+            Some((_, end)) if *end == index => *end = index + 1,
+            _ => spans.push((index, index + 1)),
+        }
+    }
+    spans
+}
+
+/// Which part of the UI currently receives key input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Pattern,
+    Results,
+    TypePicker,
+}
+
+/// Per-type toggle in the file-type picker overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TypeSelection {
+    Unset,
+    Include,
+    Exclude,
+}
+
+/// Which file types to scope the walk to, mirroring ripgrep's `--type`/
+/// `--type-not`.
+#[derive(Debug, Clone, Default)]
+pub struct TypeFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+/// The names of every file type ripgrep knows about, for the type picker.
+fn default_type_names() -> Vec<String> {
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+    let mut names: Vec<String> =
+        builder.definitions().iter().map(|def| def.name().to_string()).collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Build the `ignore::types::Types` matcher the walker should scope itself
+/// to, from ripgrep's bundled type definitions plus the current selection.
+fn build_types(filter: &TypeFilter) -> Result<Types, ignore::Error> {
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+    for name in &filter.include {
+        builder.select(name);
+    }
+    for name in &filter.exclude {
+        builder.negate(name);
+    }
+    builder.build()
+}
+
+/// Drop any `--type`/`--type-not` name in `filter` that isn't one of
+/// `known` (ripgrep's built-in type names), printing a diagnostic for each.
+///
+/// This has to happen before `filter` ever reaches `build_types`: that runs
+/// on the background search thread, which has no way to show an error in
+/// the UI and, left unhandled, would otherwise propagate out of
+/// `handle_search` and hard-exit the whole process via `std::process::exit`
+/// without ever calling `disable_raw_mode`, leaving the user's terminal
+/// stuck in raw mode.
+fn validate_type_filter(filter: TypeFilter, known: &[String]) -> TypeFilter {
+    let keep = |names: Vec<String>, flag: &str| -> Vec<String> {
+        names
+            .into_iter()
+            .filter(|name| {
+                let recognized = known.iter().any(|known_name| known_name == name);
+                if !recognized {
+                    eprintln!("ignoring unknown file type for --{flag}: {name}");
+                }
+                recognized
+            })
+            .collect()
+    };
+    TypeFilter {
+        include: keep(filter.include, "type"),
+        exclude: keep(filter.exclude, "type-not"),
+    }
+}
+
+/// Set `name`'s toggle in the type picker. `type_states` is seeded with
+/// every name `default_type_names()` knows about, so in practice this is
+/// always a lookup -- the `None` arm only matters if `name` wasn't one of
+/// them.
+fn set_type_selection(
+    states: &mut Vec<(String, TypeSelection)>,
+    name: &str,
+    selection: TypeSelection,
+) {
+    match states.iter_mut().find(|(n, _)| n == name) {
+        Some(entry) => entry.1 = selection,
+        None => states.push((name.to_owned(), selection)),
+    }
+}
+
+/// Collect the current picker toggles into the `TypeFilter` a search should
+/// run with.
+fn current_type_filter(states: &[(String, TypeSelection)]) -> TypeFilter {
+    let mut filter = TypeFilter::default();
+    for (name, selection) in states {
+        match selection {
+            TypeSelection::Include => filter.include.push(name.clone()),
+            TypeSelection::Exclude => filter.exclude.push(name.clone()),
+            TypeSelection::Unset => {}
+        }
+    }
+    filter
+}
+
+/// Launch `$EDITOR` (falling back to `$VISUAL`, then `vi`) at `path:line`,
+/// blocking until the editor exits.
+fn open_in_editor(path: &OsStr, line: u64) -> eyre::Result<()> {
+    let editor = std::env::var_os("EDITOR")
+        .or_else(|| std::env::var_os("VISUAL"))
+        .unwrap_or_else(|| OsString::from("vi"));
+
+    let editor_name = Path::new(&editor).file_name().and_then(OsStr::to_str).unwrap_or("");
+
+    let mut cmd = Command::new(&editor);
+    match editor_name {
+        "code" | "code-insiders" => {
+            cmd.arg("-g").arg(format!("{}:{line}", Path::new(path).display()));
+        }
+        _ => {
+            cmd.arg(format!("+{line}")).arg(path);
+        }
+    }
+
+    cmd.status()?;
+    Ok(())
+}
+
+/// Interactive toggles for the regex pipeline, echoed in the "Pattern" block
+/// title so the user can see which are active.
+#[derive(Debug, Clone, Copy, Default)]
+struct SearchOptions {
+    case_insensitive: bool,
+    whole_word: bool,
+    fixed_string: bool,
+}
+
+/// Whether the pattern field is interpreted as a regex (the default) or as a
+/// typo-tolerant fuzzy query scored by `nucleo-matcher`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SearchMode {
+    #[default]
+    Regex,
+    Fuzzy,
+}
+
+/// Build the matcher that decides which lines `Searcher` hands to the sink.
+///
+/// In `Regex` mode this is the real query, honouring the current toggles and
+/// escaping the pattern first when fixed-string mode is on. In `Fuzzy` mode
+/// every line is a candidate, so this just matches everything; the actual
+/// scoring happens in `TxSink::matched` via `nucleo-matcher`.
+fn build_matcher(
+    pattern: &str,
+    mode: SearchMode,
+    options: SearchOptions,
+) -> Result<RegexMatcher, grep::regex::Error> {
+    match mode {
+        SearchMode::Regex => {
+            let pattern =
+                if options.fixed_string { regex::escape(pattern) } else { pattern.to_owned() };
+
+            RegexMatcherBuilder::new()
+                .case_insensitive(options.case_insensitive)
+                .word(options.whole_word)
+                .build(&pattern)
+        }
+        SearchMode::Fuzzy => RegexMatcherBuilder::new().build(".*"),
+    }
+}
+
 enum SearchState {
-    New { pattern: String, paths: Arc<Vec<OsString>> },
-    InProgress { pattern: String },
+    New {
+        pattern: String,
+        paths: Arc<Vec<OsString>>,
+        mode: SearchMode,
+        options: SearchOptions,
+        types: TypeFilter,
+        context: ContextLines,
+        generation: u64,
+    },
+    InProgress {
+        pattern: String,
+        generation: u64,
+    },
     Done,
 }
 
@@ -56,10 +538,32 @@ impl SinkError for TxSinkError {
 struct TxSink {
     path: OsString,
     tx: Sender<UiEvent>,
+    matcher: RegexMatcher,
+    mode: SearchMode,
+    query: String,
+    fuzzy_matcher: FuzzyMatcher,
+    generation: u64,
 }
 
 impl TxSink {
-    fn new(path: &OsStr, tx: Sender<UiEvent>) -> Self { TxSink { path: path.to_owned(), tx } }
+    fn new(
+        path: &OsStr,
+        tx: Sender<UiEvent>,
+        matcher: RegexMatcher,
+        mode: SearchMode,
+        query: String,
+        generation: u64,
+    ) -> Self {
+        TxSink {
+            path: path.to_owned(),
+            tx,
+            matcher,
+            mode,
+            query,
+            fuzzy_matcher: FuzzyMatcher::new(FuzzyConfig::DEFAULT),
+            generation,
+        }
+    }
 }
 
 impl Sink for TxSink {
@@ -67,19 +571,82 @@ impl Sink for TxSink {
 
     fn matched(&mut self, _searcher: &Searcher, mat: &SinkMatch) -> Result<bool, Self::Error> {
         let res = String::from_utf8_lossy(mat.bytes());
+
+        let (spans, score) = match self.mode {
+            SearchMode::Regex => {
+                let mut byte_spans = Vec::new();
+                self.matcher
+                    .find_iter(mat.bytes(), |m| {
+                        byte_spans.push((m.start(), m.end()));
+                        true
+                    })
+                    .map_err(|err| TxSinkError(err.to_string()))?;
+
+                // `res` is a lossy UTF-8 decode of the same bytes `byte_spans`
+                // was computed over, so char offsets line up as long as the
+                // line is valid UTF-8 (the overwhelmingly common case for
+                // source files).
+                let spans = byte_spans
+                    .into_iter()
+                    .map(|(start, end)| {
+                        let char_offset = |byte_idx: usize| {
+                            res.char_indices().take_while(|(i, _)| *i < byte_idx).count()
+                        };
+                        (char_offset(start), char_offset(end))
+                    })
+                    .collect();
+                (spans, None)
+            }
+            SearchMode::Fuzzy => {
+                let line = res.trim_end_matches(['\n', '\r']);
+                let mut haystack_buf = Vec::new();
+                let mut needle_buf = Vec::new();
+                let haystack = Utf32Str::new(line, &mut haystack_buf);
+                let needle = Utf32Str::new(&self.query, &mut needle_buf);
+
+                let mut indices = Vec::new();
+                let Some(score) = self.fuzzy_matcher.fuzzy_indices(haystack, needle, &mut indices)
+                else {
+                    return Ok(true); // below the match threshold, not a hit
+                };
+                (fuzzy_indices_to_spans(&indices), Some(score))
+            }
+        };
+
         let ev = UiEvent::MatchFound {
             path: self.path.clone(),
             line: mat.line_number().unwrap_or_default(),
             text: res.to_string(),
+            spans,
+            score,
+            generation: self.generation,
+        };
+        self.tx.send(ev).map_err(|err| TxSinkError(err.to_string()))?;
+        Ok(true)
+    }
+
+    fn context(&mut self, _searcher: &Searcher, ctx: &SinkContext) -> Result<bool, Self::Error> {
+        let ev = UiEvent::ContextFound {
+            path: self.path.clone(),
+            line: ctx.line_number().unwrap_or_default(),
+            text: String::from_utf8_lossy(ctx.bytes()).to_string(),
+            generation: self.generation,
         };
         self.tx.send(ev).map_err(|err| TxSinkError(err.to_string()))?;
         Ok(true)
     }
+
+    fn context_break(&mut self, _searcher: &Searcher) -> Result<bool, Self::Error> {
+        let ev = UiEvent::ContextBreak { path: self.path.clone(), generation: self.generation };
+        self.tx.send(ev).map_err(|err| TxSinkError(err.to_string()))?;
+        Ok(true)
+    }
 }
 
 struct Events {
     ui_events: Receiver<UiEvent>,
     search_state: Arc<(Mutex<SearchState>, Condvar)>,
+    input_suspend: Arc<(Mutex<bool>, Condvar)>,
     _input_handle: thread::JoinHandle<()>,
     _result_handle: thread::JoinHandle<()>,
 }
@@ -88,13 +655,26 @@ impl Events {
     fn new() -> Events {
         let (ui_tx, ui_rx) = bounded(1000);
         let search_state = Arc::new((Mutex::new(SearchState::Done), Condvar::new()));
+        let input_suspend = Arc::new((Mutex::new(false), Condvar::new()));
 
         let input_handle = {
             let tx = ui_tx.clone();
+            let input_suspend = input_suspend.clone();
             thread::spawn(move || {
                 let handle_events = || -> eyre::Result<()> {
                     let mut last_tick = Instant::now();
                     loop {
+                        // While a spawned editor owns the terminal (see
+                        // `Events::suspend_input`), stop polling/reading stdin so this
+                        // thread doesn't race the child process for keystrokes.
+                        {
+                            let (suspended, resumed) = &*input_suspend;
+                            let mut suspended = suspended.lock();
+                            while *suspended {
+                                resumed.wait(&mut suspended);
+                            }
+                        }
+
                         if event::poll(
                             TICK_RATE.checked_sub(last_tick.elapsed()).unwrap_or_default(),
                         )? {
@@ -131,25 +711,53 @@ impl Events {
             thread::spawn(move || {
                 let handle_search = || -> eyre::Result<()> {
                     loop {
-                        let (search_pattern, search_paths) = {
+                        let (
+                            search_pattern,
+                            search_paths,
+                            search_mode,
+                            search_options,
+                            search_types,
+                            search_context,
+                            search_generation,
+                        ) = {
                             let (search_mutex, start_anew) = &*search_state;
                             let mut state = search_mutex.lock();
 
                             let search_pattern: String;
                             let search_paths: Arc<Vec<OsString>>;
+                            let search_mode: SearchMode;
+                            let search_options: SearchOptions;
+                            let search_types: TypeFilter;
+                            let search_context: ContextLines;
+                            let search_generation: u64;
 
                             match &*state {
                                 SearchState::New { pattern, .. } if pattern.is_empty() => {
                                     *state = SearchState::Done;
                                     continue;
                                 }
-                                SearchState::New { pattern, paths } => {
+                                SearchState::New {
+                                    pattern,
+                                    paths,
+                                    mode,
+                                    options,
+                                    types,
+                                    context,
+                                    generation,
+                                } => {
                                     search_pattern = pattern.to_owned();
                                     search_paths = paths.clone();
-                                    *state =
-                                        SearchState::InProgress { pattern: pattern.to_owned() };
+                                    search_mode = *mode;
+                                    search_options = *options;
+                                    search_types = types.clone();
+                                    search_context = *context;
+                                    search_generation = *generation;
+                                    *state = SearchState::InProgress {
+                                        pattern: pattern.to_owned(),
+                                        generation: *generation,
+                                    };
                                 }
-                                SearchState::InProgress { pattern } => {
+                                SearchState::InProgress { pattern, .. } => {
                                     unreachable!(
                                         "landed in middle of in-progress search: {}",
                                         pattern
@@ -161,21 +769,35 @@ impl Events {
                                 }
                             }
 
-                            (search_pattern, search_paths)
+                            (
+                                search_pattern,
+                                search_paths,
+                                search_mode,
+                                search_options,
+                                search_types,
+                                search_context,
+                                search_generation,
+                            )
                         };
 
                         // validate once here, so that we can simply unwrap in each parallel worker
                         // later
-                        let _ = RegexMatcher::new_line_matcher(&search_pattern)?;
+                        let _ = build_matcher(&search_pattern, search_mode, search_options)?;
+                        let types = build_types(&search_types)?;
 
                         let (first, rest) = search_paths.split_first().expect("empty path list");
                         let mut walker = WalkBuilder::new(first);
                         for path in rest {
                             walker.add(path);
                         }
+                        walker.types(types);
                         walker.build_parallel().run(|| {
                             let tx = ui_tx.clone();
                             let search_pattern = search_pattern.clone();
+                            let search_mode = search_mode;
+                            let search_options = search_options;
+                            let search_context = search_context;
+                            let search_generation = search_generation;
                             let search_state = search_state.clone();
 
                             Box::new(move |entry: Result<DirEntry, ignore::Error>| {
@@ -197,13 +819,22 @@ impl Events {
                                     return WalkState::Quit;
                                 }
 
-                                let sink = TxSink::new(entry.path().as_os_str(), tx.clone());
-
                                 let matcher =
-                                    RegexMatcher::new_line_matcher(&search_pattern).unwrap();
+                                    build_matcher(&search_pattern, search_mode, search_options)
+                                        .unwrap();
+                                let sink = TxSink::new(
+                                    entry.path().as_os_str(),
+                                    tx.clone(),
+                                    matcher.clone(),
+                                    search_mode,
+                                    search_pattern.clone(),
+                                    search_generation,
+                                );
                                 let mut searcher = SearcherBuilder::new()
                                     .binary_detection(BinaryDetection::quit(b'\x00'))
                                     .line_number(true)
+                                    .before_context(search_context.before)
+                                    .after_context(search_context.after)
                                     .build();
 
                                 searcher.search_path(&matcher, entry.path(), sink).unwrap_or_else(
@@ -216,7 +847,21 @@ impl Events {
                             })
                         });
 
-                        *search_state.0.lock() = SearchState::Done;
+                        // Only clear the state if it's still the `InProgress` marker this
+                        // search itself set -- the UI thread may have already raced in a
+                        // fresh `New` (a debounced keystroke firing while this walk was
+                        // being cancelled). Overwriting that `New` with `Done` would lose
+                        // it: the next loop iteration would `wait` on a notify that
+                        // already fired, and the queued search wouldn't run until some
+                        // *later* edit happened to win the next race.
+                        let mut state = search_state.0.lock();
+                        if matches!(
+                            &*state,
+                            SearchState::InProgress { generation, .. }
+                                if *generation == search_generation
+                        ) {
+                            *state = SearchState::Done;
+                        }
                     }
                 };
 
@@ -230,6 +875,7 @@ impl Events {
         Events {
             ui_events: ui_rx,
             search_state,
+            input_suspend,
             _input_handle: input_handle,
             _result_handle: result_handle,
         }
@@ -237,35 +883,431 @@ impl Events {
 
     fn next(&self) -> Result<UiEvent, crossbeam_channel::RecvError> { self.ui_events.recv() }
 
-    fn new_search<P>(&mut self, pattern: P, paths: Arc<Vec<OsString>>) -> eyre::Result<()>
+    /// Stop the input thread from polling/reading stdin, so a spawned editor
+    /// subprocess can own the controlling terminal without racing it for
+    /// keystrokes.
+    fn suspend_input(&self) {
+        *self.input_suspend.0.lock() = true;
+    }
+
+    /// Resume polling/reading stdin after the editor subprocess has exited.
+    fn resume_input(&self) {
+        *self.input_suspend.0.lock() = false;
+        self.input_suspend.1.notify_one();
+    }
+
+    fn new_search<P>(
+        &mut self,
+        pattern: P,
+        paths: Arc<Vec<OsString>>,
+        mode: SearchMode,
+        options: SearchOptions,
+        types: TypeFilter,
+        context: ContextLines,
+        generation: u64,
+    ) -> eyre::Result<()>
     where
         P: Into<String>,
     {
-        *self.search_state.0.lock() = SearchState::New { pattern: pattern.into(), paths };
+        *self.search_state.0.lock() = SearchState::New {
+            pattern: pattern.into(),
+            paths,
+            mode,
+            options,
+            types,
+            context,
+            generation,
+        };
         self.search_state.1.notify_one();
         Ok(())
     }
 }
 
+/// Carve a centered `percent_x` x `percent_y` rectangle out of `area`, for
+/// overlay popups.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
 pub struct App {
     events: Events,
     pattern: String,
     search_paths: Arc<Vec<OsString>>,
-    results: Vec<String>,
+    search_mode: SearchMode,
+    search_options: SearchOptions,
+    results: Vec<ResultLine>,
+    /// Per-file buffering that `results` (in `SearchMode::Regex`) is
+    /// flattened from, so concurrent walker threads can't interleave two
+    /// files' groups. See `ResultGroups`.
+    result_groups: ResultGroups,
+    /// Bounded top-N ranking of the current fuzzy search, kept alongside
+    /// `results` (which it rebuilds into) so a later, lower-scoring match
+    /// can't push an earlier, better one off the list.
+    fuzzy_heap: BinaryHeap<Reverse<FuzzyHit>>,
+    /// Whether `fuzzy_heap` has grown since `results` was last re-derived
+    /// from it, checked on `Tick` by `rebuild_fuzzy_results`.
+    fuzzy_dirty: bool,
+    /// Whether `result_groups` has changed since `results` was last
+    /// flattened from it, checked on `Tick` by `rebuild_grouped_results`.
+    results_dirty: bool,
+    list_state: ListState,
+    focus: Focus,
+    type_states: Vec<(String, TypeSelection)>,
+    type_picker_state: ListState,
+    context: ContextLines,
+    syntax_set: SyntaxSet,
+    theme_set: ThemeSet,
+    preview: Option<Preview>,
+    search_generation: u64,
+    search_dirty: bool,
+    last_edit: Instant,
 }
 
 impl App {
-    pub fn new(search_paths: Vec<OsString>, initial_pattern: Option<String>) -> eyre::Result<Self> {
+    pub fn new(
+        search_paths: Vec<OsString>,
+        initial_pattern: Option<String>,
+        type_filter: TypeFilter,
+        context: ContextLines,
+    ) -> eyre::Result<Self> {
         let search_paths = Arc::new(search_paths);
         let mut events = Events::new();
+        let search_mode = SearchMode::default();
+        let search_options = SearchOptions::default();
+
+        let known_types = default_type_names();
+        let type_filter = validate_type_filter(type_filter, &known_types);
+
+        let mut type_states: Vec<(String, TypeSelection)> =
+            known_types.into_iter().map(|name| (name, TypeSelection::Unset)).collect();
+        for name in &type_filter.include {
+            set_type_selection(&mut type_states, name, TypeSelection::Include);
+        }
+        for name in &type_filter.exclude {
+            set_type_selection(&mut type_states, name, TypeSelection::Exclude);
+        }
+
+        let mut type_picker_state = ListState::default();
+        if !type_states.is_empty() {
+            type_picker_state.select(Some(0));
+        }
+
+        let mut search_generation = 0;
         let pattern = if let Some(pattern) = initial_pattern {
-            events.new_search(&pattern, search_paths.clone())?;
+            search_generation += 1;
+            events.new_search(
+                &pattern,
+                search_paths.clone(),
+                search_mode,
+                search_options,
+                current_type_filter(&type_states),
+                context,
+                search_generation,
+            )?;
             pattern
         } else {
             String::new()
         };
 
-        Ok(Self { events, pattern, search_paths, results: Vec::new() })
+        Ok(Self {
+            events,
+            pattern,
+            search_paths,
+            search_mode,
+            search_options,
+            results: Vec::new(),
+            result_groups: ResultGroups::default(),
+            fuzzy_heap: BinaryHeap::new(),
+            fuzzy_dirty: false,
+            results_dirty: false,
+            list_state: ListState::default(),
+            focus: Focus::Pattern,
+            type_states,
+            type_picker_state,
+            context,
+            syntax_set: SyntaxSet::load_defaults_newlines(),
+            theme_set: ThemeSet::load_defaults(),
+            preview: None,
+            search_generation,
+            search_dirty: false,
+            last_edit: Instant::now(),
+        })
+    }
+
+    fn select_next(&mut self) {
+        if self.results.is_empty() {
+            return;
+        }
+        let next = self.list_state.selected().map_or(0, |i| (i + 1).min(self.results.len() - 1));
+        self.list_state.select(Some(next));
+    }
+
+    fn select_prev(&mut self) {
+        if self.results.is_empty() {
+            return;
+        }
+        let prev = self.list_state.selected().map_or(0, |i| i.saturating_sub(1));
+        self.list_state.select(Some(prev));
+    }
+
+    fn select_next_page(&mut self, page: usize) {
+        if self.results.is_empty() {
+            return;
+        }
+        let next =
+            self.list_state.selected().map_or(0, |i| (i + page).min(self.results.len() - 1));
+        self.list_state.select(Some(next));
+    }
+
+    fn select_prev_page(&mut self, page: usize) {
+        if self.results.is_empty() {
+            return;
+        }
+        let prev = self.list_state.selected().map_or(0, |i| i.saturating_sub(page));
+        self.list_state.select(Some(prev));
+    }
+
+    fn open_selected(&mut self) -> eyre::Result<()> {
+        let selected = self.list_state.selected().and_then(|i| self.results.get(i));
+        let Some(ResultLine::Match(mat)) = selected else {
+            return Ok(());
+        };
+
+        self.events.suspend_input();
+        disable_raw_mode()?;
+        let result = open_in_editor(&mat.path, mat.line);
+        enable_raw_mode()?;
+        self.events.resume_input();
+        result
+    }
+
+    /// Re-render the preview pane if the selected result has changed since
+    /// it was last built. Called on `UiEvent::Tick` so preview rendering
+    /// doesn't run on every keystroke while scrolling through results.
+    fn update_preview(&mut self) {
+        let Some(selected) = self.list_state.selected() else {
+            self.preview = None;
+            return;
+        };
+
+        let Some(ResultLine::Match(mat)) = self.results.get(selected) else {
+            return;
+        };
+        let path = mat.path.clone();
+        let line = mat.line;
+
+        if self.preview.as_ref().is_some_and(|preview| preview.path == path && preview.line == line)
+        {
+            return;
+        }
+
+        match highlight_preview(&self.syntax_set, &self.theme_set, &path, line) {
+            Ok(lines) => self.preview = Some(Preview { path, line, lines }),
+            Err(err) => {
+                eprintln!("failed to render preview: {err}");
+                self.preview = None;
+            }
+        }
+    }
+
+    /// Restart the current search with the current pattern under the
+    /// current `search_mode`/`search_options`/type selection (used after
+    /// toggling a search mode or a file type).
+    fn restart_search(&mut self) -> eyre::Result<()> {
+        self.results.clear();
+        self.result_groups.clear();
+        self.fuzzy_heap.clear();
+        self.fuzzy_dirty = false;
+        self.results_dirty = false;
+        self.list_state.select(None);
+        self.preview = None;
+        self.search_dirty = false;
+        self.fire_search()
+    }
+
+    /// Send the current pattern/mode/options to the search thread under a
+    /// fresh generation id, so stale results from a superseded search can be
+    /// told apart from current ones.
+    fn fire_search(&mut self) -> eyre::Result<()> {
+        self.search_generation += 1;
+        self.events.new_search(
+            &self.pattern,
+            self.search_paths.clone(),
+            self.search_mode,
+            self.search_options,
+            current_type_filter(&self.type_states),
+            self.context,
+            self.search_generation,
+        )
+    }
+
+    /// Mark the pattern as changed so a debounced search fires once it's
+    /// been stable for `SEARCH_DEBOUNCE`, and clear stale results right away.
+    fn mark_pattern_edited(&mut self) {
+        self.results.clear();
+        self.result_groups.clear();
+        self.fuzzy_heap.clear();
+        self.fuzzy_dirty = false;
+        self.results_dirty = false;
+        self.list_state.select(None);
+        self.preview = None;
+        self.search_dirty = true;
+        self.last_edit = Instant::now();
+    }
+
+    /// The `(path, line)` identity of the selected match, if any, used to
+    /// keep the selection pinned to the same match across a `results`
+    /// rebuild even as rows shift position around it.
+    fn selected_match_identity(&self) -> Option<(OsString, u64)> {
+        self.list_state.selected().and_then(|i| self.results.get(i)).and_then(|line| match line {
+            ResultLine::Match(mat) => Some((mat.path.clone(), mat.line)),
+            _ => None,
+        })
+    }
+
+    /// Re-point the selection at the row now holding `identity` after a
+    /// `results` rebuild, falling back to the top row if it's gone missing
+    /// (e.g. evicted from the fuzzy top-N) and there's something to select.
+    fn reselect_by_identity(&mut self, identity: Option<(OsString, u64)>) {
+        match identity {
+            Some((path, line)) => {
+                let new_index = self.results.iter().position(|result| {
+                    matches!(result, ResultLine::Match(mat) if mat.path == path && mat.line == line)
+                });
+                self.list_state.select(new_index);
+            }
+            None if !self.results.is_empty() => self.list_state.select(Some(0)),
+            None => {}
+        }
+    }
+
+    /// Insert a fuzzy hit into the bounded top-N heap, evicting the
+    /// lowest-scoring entry if it's now over `FUZZY_RESULT_LIMIT`.
+    ///
+    /// In `SearchMode::Fuzzy` the matcher is `.*`, so this runs for every
+    /// line of every walked file, not just hits -- re-deriving `results`
+    /// (an O(`FUZZY_RESULT_LIMIT`) sort-and-clone) on every single call would
+    /// flood the UI thread long before the next `Tick` gets a chance to
+    /// redraw. So this only updates the heap; `rebuild_fuzzy_results` does
+    /// the actual re-derive, coalesced onto `Tick`.
+    fn push_fuzzy_hit(&mut self, hit: FuzzyHit) {
+        self.fuzzy_heap.push(Reverse(hit));
+        if self.fuzzy_heap.len() > FUZZY_RESULT_LIMIT {
+            self.fuzzy_heap.pop();
+        }
+        self.fuzzy_dirty = true;
+    }
+
+    /// Re-derive `results` from `fuzzy_heap` in descending-score order, if
+    /// any hits have come in since the last rebuild. Called on `Tick` so a
+    /// burst of fuzzy hits only costs one sort-and-clone per tick instead of
+    /// one per line walked.
+    fn rebuild_fuzzy_results(&mut self) {
+        if !self.fuzzy_dirty {
+            return;
+        }
+        self.fuzzy_dirty = false;
+
+        let selected = self.selected_match_identity();
+
+        let mut hits: Vec<&FuzzyHit> = self.fuzzy_heap.iter().map(|Reverse(hit)| hit).collect();
+        hits.sort_by(|a, b| b.cmp(a));
+        self.results = hits
+            .into_iter()
+            .map(|hit| {
+                ResultLine::Match(Match {
+                    path: hit.path.clone(),
+                    line: hit.line,
+                    text: hit.text.clone(),
+                    spans: hit.spans.clone(),
+                })
+            })
+            .collect();
+
+        self.reselect_by_identity(selected);
+    }
+
+    /// Buffer a non-fuzzy match under its file's group, so a
+    /// concurrently-arriving line from another file can't land in the
+    /// middle of this one's group. Just marks `results` stale --
+    /// `rebuild_grouped_results` does the actual (full-clone) flatten,
+    /// coalesced onto `Tick` for the same reason `rebuild_fuzzy_results` is:
+    /// a match-heavy search would otherwise re-clone the whole, unbounded
+    /// result set once per incoming line and flood the UI thread.
+    fn push_match(&mut self, mat: Match) {
+        self.result_groups.push_line(&mat.path.clone(), ResultLine::Match(mat));
+        self.results_dirty = true;
+    }
+
+    /// Buffer a context line under its file's group, same as `push_match`.
+    fn push_context_line(&mut self, path: OsString, line: u64, text: String) {
+        self.result_groups.push_line(&path.clone(), ResultLine::Context { path, line, text });
+        self.results_dirty = true;
+    }
+
+    /// Record a context gap reported for `path`, same as `push_match`.
+    fn push_context_break(&mut self, path: OsString) {
+        self.result_groups.push_break(&path);
+        self.results_dirty = true;
+    }
+
+    /// Re-flatten `results` from `result_groups`, if any grouped line has
+    /// come in since the last rebuild. Called on `Tick` so a burst of
+    /// matches/context lines only costs one flatten-and-clone per tick
+    /// instead of one per line walked.
+    fn rebuild_grouped_results(&mut self) {
+        if !self.results_dirty {
+            return;
+        }
+        self.results_dirty = false;
+
+        let selected = self.selected_match_identity();
+        self.results = self.result_groups.flatten();
+        self.reselect_by_identity(selected);
+    }
+
+    fn type_picker_select_next(&mut self) {
+        if self.type_states.is_empty() {
+            return;
+        }
+        let next = self
+            .type_picker_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(self.type_states.len() - 1));
+        self.type_picker_state.select(Some(next));
+    }
+
+    fn type_picker_select_prev(&mut self) {
+        if self.type_states.is_empty() {
+            return;
+        }
+        let prev = self.type_picker_state.selected().map_or(0, |i| i.saturating_sub(1));
+        self.type_picker_state.select(Some(prev));
+    }
+
+    fn toggle_type_selection(&mut self, target: TypeSelection) {
+        let Some(entry) =
+            self.type_picker_state.selected().and_then(|i| self.type_states.get_mut(i))
+        else {
+            return;
+        };
+        entry.1 = if entry.1 == target { TypeSelection::Unset } else { target };
     }
 
     pub fn render(&mut self) -> eyre::Result<()> {
@@ -290,22 +1332,104 @@ impl App {
                     ])
                     .split(dimensions);
 
+                let main_chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(chunks[0]);
+
                 let results_title = format!("Results ({})", self.results.len());
                 let results = self
                     .results
                     .iter()
-                    .take(usize::from(dimensions.height) - 3)
-                    .map(Text::raw)
-                    .map(ListItem::new)
+                    .map(|line| match line {
+                        ResultLine::Match(mat) => {
+                            let prefix = format!("{}:{} ", mat.path.to_string_lossy(), mat.line);
+                            let mut spans = vec![Span::raw(prefix)];
+                            spans.extend(highlight_spans(&mat.text, &mat.spans));
+                            ListItem::new(Line::from(spans))
+                        }
+                        ResultLine::Context { path, line, text } => {
+                            let prefix = format!("{}:{} ", path.to_string_lossy(), line);
+                            let style = Style::default().fg(Color::DarkGray);
+                            let spans =
+                                vec![Span::styled(prefix, style), Span::styled(text, style)];
+                            ListItem::new(Line::from(spans))
+                        }
+                        ResultLine::Separator => {
+                            ListItem::new(Line::from(Span::styled(
+                                "--",
+                                Style::default().fg(Color::DarkGray),
+                            )))
+                        }
+                    })
                     .collect::<Vec<_>>();
                 let results_list = List::new(results)
-                    .block(Block::default().borders(Borders::ALL).title(results_title));
-                f.render_widget(results_list, chunks[0]);
+                    .block(Block::default().borders(Borders::ALL).title(results_title))
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                    .highlight_symbol("> ");
+                f.render_stateful_widget(results_list, main_chunks[0], &mut self.list_state);
+
+                let selected = self.list_state.selected().and_then(|i| self.results.get(i));
+                let preview_title = match selected {
+                    Some(ResultLine::Match(mat)) => mat.path.to_string_lossy().into_owned(),
+                    _ => "Preview".to_string(),
+                };
+                let preview_lines =
+                    self.preview.as_ref().map(|preview| preview.lines.clone()).unwrap_or_default();
+                let preview = Paragraph::new(Text::from(preview_lines))
+                    .block(Block::default().borders(Borders::ALL).title(preview_title));
+                f.render_widget(preview, main_chunks[1]);
+
+                let mut toggles = Vec::new();
+                if self.search_mode == SearchMode::Fuzzy {
+                    toggles.push("fuzzy");
+                }
+                // -i/-w/-F don't apply to fuzzy matching, so don't advertise them as
+                // active while fuzzy mode is on, even if a prior regex search set them.
+                if self.search_mode == SearchMode::Regex {
+                    if self.search_options.case_insensitive {
+                        toggles.push("-i");
+                    }
+                    if self.search_options.whole_word {
+                        toggles.push("-w");
+                    }
+                    if self.search_options.fixed_string {
+                        toggles.push("-F");
+                    }
+                }
+                let pattern_title = if toggles.is_empty() {
+                    "Pattern".to_string()
+                } else {
+                    format!("Pattern [{}]", toggles.join(" "))
+                };
 
                 let input = Paragraph::new(Text::raw(&self.pattern))
                     .style(Style::default().fg(Color::Yellow))
-                    .block(Block::default().borders(Borders::ALL).title("Pattern"));
+                    .block(Block::default().borders(Borders::ALL).title(pattern_title));
                 f.render_widget(input, chunks[1]);
+
+                if self.focus == Focus::TypePicker {
+                    let popup = centered_rect(50, 70, dimensions);
+                    let items = self
+                        .type_states
+                        .iter()
+                        .map(|(name, selection)| {
+                            let marker = match selection {
+                                TypeSelection::Unset => "  ",
+                                TypeSelection::Include => "+ ",
+                                TypeSelection::Exclude => "- ",
+                            };
+                            ListItem::new(format!("{marker}{name}"))
+                        })
+                        .collect::<Vec<_>>();
+                    let picker = List::new(items)
+                        .block(Block::default().borders(Borders::ALL).title(
+                            "File types (space: include, x: exclude, esc: close)",
+                        ))
+                        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+                    f.render_widget(Clear, popup);
+                    f.render_stateful_widget(picker, popup, &mut self.type_picker_state);
+                }
             })?;
 
             // cursor
@@ -330,32 +1454,200 @@ impl App {
                             {
                                 self.pattern.clear();
                                 self.results.clear();
+                                self.result_groups.clear();
+                                self.fuzzy_heap.clear();
+                                self.fuzzy_dirty = false;
+                                self.results_dirty = false;
+                                self.list_state.select(None);
+                                self.preview = None;
+                                self.search_dirty = false;
+                                self.fire_search()?;
+                                break;
+                            }
+                            KeyCode::Tab
+                                if ev.kind == KeyEventKind::Press
+                                    && self.focus != Focus::TypePicker =>
+                            {
+                                self.focus = match self.focus {
+                                    Focus::Pattern => Focus::Results,
+                                    Focus::Results | Focus::TypePicker => Focus::Pattern,
+                                };
+                                break;
+                            }
+                            KeyCode::F(2) if ev.kind == KeyEventKind::Press => {
+                                self.focus = if self.focus == Focus::TypePicker {
+                                    Focus::Pattern
+                                } else {
+                                    Focus::TypePicker
+                                };
+                                break;
+                            }
+                            KeyCode::Up
+                                if self.focus == Focus::TypePicker
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
+                            {
+                                self.type_picker_select_prev();
+                                break;
+                            }
+                            KeyCode::Down
+                                if self.focus == Focus::TypePicker
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
+                            {
+                                self.type_picker_select_next();
+                                break;
+                            }
+                            KeyCode::Char(' ')
+                                if self.focus == Focus::TypePicker
+                                    && ev.kind == KeyEventKind::Press =>
+                            {
+                                self.toggle_type_selection(TypeSelection::Include);
+                                self.restart_search()?;
+                                break;
+                            }
+                            KeyCode::Char('x')
+                                if self.focus == Focus::TypePicker
+                                    && ev.kind == KeyEventKind::Press =>
+                            {
+                                self.toggle_type_selection(TypeSelection::Exclude);
+                                self.restart_search()?;
+                                break;
+                            }
+                            KeyCode::Esc if self.focus == Focus::TypePicker => {
+                                self.focus = Focus::Pattern;
+                                break;
+                            }
+                            // Ctrl-I is indistinguishable from Tab on the wire (both are
+                            // ASCII 0x09) without the kitty keyboard-disambiguation
+                            // protocol, which this app doesn't enable, so this toggle
+                            // uses Alt instead -- Alt-prefixed keys arrive as a separate
+                            // Esc-prefixed sequence and don't collide with Tab.
+                            // case-insensitive/whole-word/fixed-string only mean anything to
+                            // `build_matcher`'s `Regex` arm -- in `Fuzzy` mode every line is a
+                            // candidate and nucleo does its own scoring, so these toggles are
+                            // left alone (not cleared) but inert until the user switches back.
+                            KeyCode::Char('i')
+                                if ev.kind == KeyEventKind::Press
+                                    && ev.modifiers == KeyModifiers::ALT
+                                    && self.search_mode == SearchMode::Regex =>
+                            {
+                                self.search_options.case_insensitive =
+                                    !self.search_options.case_insensitive;
+                                self.restart_search()?;
+                                break;
+                            }
+                            KeyCode::Char('w')
+                                if ev.kind == KeyEventKind::Press
+                                    && ev.modifiers == KeyModifiers::CONTROL
+                                    && self.search_mode == SearchMode::Regex =>
+                            {
+                                self.search_options.whole_word = !self.search_options.whole_word;
+                                self.restart_search()?;
+                                break;
+                            }
+                            KeyCode::Char('f')
+                                if ev.kind == KeyEventKind::Press
+                                    && ev.modifiers == KeyModifiers::CONTROL
+                                    && self.search_mode == SearchMode::Regex =>
+                            {
+                                self.search_options.fixed_string =
+                                    !self.search_options.fixed_string;
+                                self.restart_search()?;
+                                break;
+                            }
+                            KeyCode::Char('t')
+                                if ev.kind == KeyEventKind::Press
+                                    && ev.modifiers == KeyModifiers::CONTROL =>
+                            {
+                                self.search_mode = match self.search_mode {
+                                    SearchMode::Regex => SearchMode::Fuzzy,
+                                    SearchMode::Fuzzy => SearchMode::Regex,
+                                };
+                                self.restart_search()?;
+                                break;
+                            }
+                            KeyCode::Up
+                                if ev.modifiers == KeyModifiers::CONTROL
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
+                            {
+                                self.context.before = self.context.before.saturating_add(1);
+                                self.context.after = self.context.after.saturating_add(1);
+                                self.restart_search()?;
+                                break;
+                            }
+                            KeyCode::Down
+                                if ev.modifiers == KeyModifiers::CONTROL
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
+                            {
+                                self.context.before = self.context.before.saturating_sub(1);
+                                self.context.after = self.context.after.saturating_sub(1);
+                                self.restart_search()?;
+                                break;
+                            }
+                            KeyCode::Up | KeyCode::Char('k')
+                                if self.focus == Focus::Results
+                                    && !mod_keys_used
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
+                            {
+                                self.select_prev();
+                                break;
+                            }
+                            KeyCode::Down | KeyCode::Char('j')
+                                if self.focus == Focus::Results
+                                    && !mod_keys_used
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
+                            {
+                                self.select_next();
+                                break;
+                            }
+                            KeyCode::PageUp
+                                if self.focus == Focus::Results
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
+                            {
+                                self.select_prev_page(usize::from(dimensions.height).saturating_sub(3));
+                                break;
+                            }
+                            KeyCode::PageDown
+                                if self.focus == Focus::Results
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
+                            {
+                                self.select_next_page(usize::from(dimensions.height).saturating_sub(3));
+                                break;
+                            }
+                            KeyCode::Enter
+                                if self.focus == Focus::Results
+                                    && ev.kind == KeyEventKind::Press =>
+                            {
+                                self.open_selected()?;
+                                terminal.clear()?;
                                 break;
                             }
                             KeyCode::Char(ch)
-                                if !mod_keys_used
+                                if self.focus == Focus::Pattern
+                                    && !mod_keys_used
                                     && (ev.kind == KeyEventKind::Press
                                         || ev.kind == KeyEventKind::Repeat) =>
                             {
                                 self.pattern.push(ch);
-                                self.results.clear();
-                                //TODO: if the key event kind is Repeat, only trigger a new search
-                                // when the key is released
-                                self.events.new_search(&self.pattern, self.search_paths.clone())?;
+                                self.mark_pattern_edited();
                                 break;
                             }
                             KeyCode::Backspace
-                                if ev.kind == KeyEventKind::Press
-                                    || ev.kind == KeyEventKind::Repeat =>
+                                if self.focus == Focus::Pattern
+                                    && (ev.kind == KeyEventKind::Press
+                                        || ev.kind == KeyEventKind::Repeat) =>
                             {
                                 self.pattern.pop();
-                                self.results.clear();
-                                if regex::Regex::new(&self.pattern).is_ok() {
-                                    self.events
-                                        .new_search(&self.pattern, self.search_paths.clone())?;
-                                    //TODO: show in pattern block title that
-                                    // pattern is invalid
-                                }
+                                self.mark_pattern_edited();
+                                //TODO: show in pattern block title that
+                                // pattern is invalid
                                 break;
                             }
                             KeyCode::Esc => {
@@ -367,17 +1659,56 @@ impl App {
                         }
                     }
 
-                    UiEvent::MatchFound { path, line, text } => {
-                        self.results.push(format!("{}:{} {}", path.to_string_lossy(), line, text));
+                    UiEvent::MatchFound { path, line, text, spans, score, generation } => {
+                        if generation != self.search_generation {
+                            continue; // stale result from a superseded search
+                        }
+                        match score {
+                            Some(score) => {
+                                self.push_fuzzy_hit(FuzzyHit { score, path, line, text, spans });
+                            }
+                            None => {
+                                self.push_match(Match { path, line, text, spans });
+                            }
+                        }
+                    }
+
+                    UiEvent::ContextFound { path, line, text, generation } => {
+                        if generation != self.search_generation
+                            || self.search_mode == SearchMode::Fuzzy
+                        {
+                            continue; // stale, or ranked hits have no fixed neighbours
+                        }
+                        self.push_context_line(path, line, text);
+                    }
+
+                    UiEvent::ContextBreak { path, generation } => {
+                        if generation != self.search_generation
+                            || self.search_mode == SearchMode::Fuzzy
+                        {
+                            continue; // stale, or ranked hits have no fixed neighbours
+                        }
+                        self.push_context_break(path);
                     }
 
                     UiEvent::Paste(str) => {
                         self.pattern.push_str(&str);
-                        self.results.clear();
-                        self.events.new_search(&self.pattern, self.search_paths.clone())?;
+                        self.mark_pattern_edited();
                     }
 
                     UiEvent::Tick => {
+                        if self.search_dirty && self.last_edit.elapsed() >= SEARCH_DEBOUNCE {
+                            self.search_dirty = false;
+                            let pattern_ok = self.search_mode == SearchMode::Fuzzy
+                                || build_matcher(&self.pattern, self.search_mode, self.search_options)
+                                    .is_ok();
+                            if pattern_ok {
+                                self.fire_search()?;
+                            }
+                        }
+                        self.rebuild_fuzzy_results();
+                        self.rebuild_grouped_results();
+                        self.update_preview();
                         break;
                     }
                 }